@@ -1,39 +1,84 @@
+use crate::error::UnsupportedLumpVersion;
 use crate::*;
 use binrw::io::Cursor;
-use binrw::BinReaderExt;
+use binrw::{BinRead, BinReaderExt, Endian};
 use std::borrow::Cow;
+use std::collections::HashMap;
+use std::io::{Read, Write};
+
+/// The oldest BSP version this crate knows how to parse (HL2 beta).
+const MIN_VERSION: u32 = 17;
+/// The newest BSP version this crate knows how to parse (CS:GO, L4D).
+const MAX_VERSION: u32 = 21;
+
+/// Cap on the pre-allocation for a single (untrusted) pakfile entry.
+const MAX_PAK_ENTRY_PREALLOC: usize = 16 * 1024 * 1024;
 
 pub struct BspFile<'a> {
     data: &'a [u8],
     directories: Directories,
     header: Header,
+    version: u32,
+    endian: Endian,
+    lump_overrides: HashMap<LumpType, LumpOverride<'a>>,
+}
+
+/// An externally supplied replacement for a lump, as applied by `with_lump_override` or
+/// `load_lump_file`. `version` is the override's own lump version, if known, since a `.lmp`
+/// file's header can claim a different version than the directory entry it replaces.
+struct LumpOverride<'a> {
+    version: Option<u16>,
+    data: Cow<'a, [u8]>,
+}
+
+/// Detect whether `header`'s ident is the normal "VBSP" or the byte-swapped console "PSBV".
+fn detect_endian(header: Header) -> BspResult<Endian> {
+    const EXPECTED_HEADER: Header = Header {
+        v: 0x56,
+        b: 0x42,
+        s: 0x53,
+        p: 0x50,
+    };
+    const SWAPPED_HEADER: Header = Header {
+        v: 0x50,
+        b: 0x53,
+        s: 0x42,
+        p: 0x56,
+    };
+
+    if header == EXPECTED_HEADER {
+        Ok(Endian::Little)
+    } else if header == SWAPPED_HEADER {
+        Ok(Endian::Big)
+    } else {
+        Err(BspError::UnexpectedHeader(header))
+    }
 }
 
 impl<'a> BspFile<'a> {
     pub fn new(data: &'a [u8]) -> BspResult<Self> {
-        const EXPECTED_HEADER: Header = Header {
-            v: 0x56,
-            b: 0x42,
-            s: 0x53,
-            p: 0x50,
-        };
-        // TODO: Use this to decide on the version to parse it as
-        const EXPECTED_VERSION: u32 = 0x14;
-
         let mut cursor = Cursor::new(data);
         let header: Header = cursor.read_le()?;
-        let version: u32 = cursor.read_le()?;
+        let endian = detect_endian(header)?;
+
+        let version: u32 = cursor.read_type(endian)?;
 
-        if header != EXPECTED_HEADER || version != EXPECTED_VERSION {
-            return Err(BspError::UnexpectedHeader(header));
+        if !(MIN_VERSION..=MAX_VERSION).contains(&version) {
+            return Err(BspError::LumpVersion(UnsupportedLumpVersion {
+                lump_type: "bsp",
+                version: version as u16,
+            }));
         }
 
-        let directories = cursor.read_le()?;
+        let directories = cursor.read_type(endian)?;
 
         Ok(BspFile {
             data,
             directories,
             header,
+            version,
+            endian,
+            lump_overrides: HashMap::new(),
         })
     }
 
@@ -41,12 +86,26 @@ impl<'a> BspFile<'a> {
         &self.header
     }
 
+    /// The BSP version this file was parsed as, e.g. 20 for TF2/CS:S or 21 for CS:GO/L4D.
+    pub fn version(&self) -> u32 {
+        self.version
+    }
+
+    /// The byte order this file was detected as. Big-endian indicates an Xbox 360 or PS3 bsp.
+    pub fn endian(&self) -> Endian {
+        self.endian
+    }
+
     pub fn lump_reader(&self, lump: LumpType) -> BspResult<LumpReader<Cursor<Cow<[u8]>>>> {
         let data = self.get_lump(lump)?;
-        Ok(LumpReader::new(data, lump))
+        Ok(LumpReader::new(data, lump, self.version, self.endian))
     }
 
     pub fn get_lump(&self, lump: LumpType) -> BspResult<Cow<[u8]>> {
+        if let Some(over) = self.lump_overrides.get(&lump) {
+            return Ok(over.data.clone());
+        }
+
         let lump = &self.directories[lump];
         let raw_data = self
             .data
@@ -61,11 +120,127 @@ impl<'a> BspFile<'a> {
             }
         })
     }
+
+    /// Redirect `get_lump`/`lump_reader` for `lump` to read from `data` instead of the
+    /// directory-resident lump, as if it had been read from an external `.lmp` lump file.
+    /// Version-sensitive readers keep using the directory's lump version; use `load_lump_file`
+    /// if the override needs to carry its own.
+    pub fn with_lump_override(&mut self, lump: LumpType, data: &'a [u8]) {
+        self.lump_overrides.insert(
+            lump,
+            LumpOverride {
+                version: None,
+                data: Cow::Borrowed(data),
+            },
+        );
+    }
+
+    /// The lump version to parse `lump` with: the override's own version if one applies and
+    /// carried one, otherwise the directory's.
+    fn lump_version(&self, lump: LumpType) -> u16 {
+        self.lump_overrides
+            .get(&lump)
+            .and_then(|over| over.version)
+            .unwrap_or(self.directories[lump].version as u16)
+    }
+
+    /// Parse an external `.lmp` lump file, such as `mapname_l_0.lmp`, and apply it as an
+    /// override for the lump it targets. Mappers distribute these to patch a single lump,
+    /// most commonly entities, without shipping the whole rebuilt bsp.
+    pub fn load_lump_file(&mut self, data: &'a [u8]) -> BspResult<()> {
+        let header: LumpFileHeader = match self.endian {
+            Endian::Little => Cursor::new(data).read_le()?,
+            Endian::Big => Cursor::new(data).read_be()?,
+        };
+
+        let lump = LumpType::ALL
+            .get(header.lump_id as usize)
+            .copied()
+            .ok_or(BspError::UnknownLumpFileId(header.lump_id))?;
+
+        let out_of_bounds = || BspError::LumpFileOutOfBounds {
+            offset: header.lump_offset,
+            length: header.lump_length,
+        };
+
+        // Untrusted external data: validate with checked arithmetic, not a raw cast-and-add.
+        let start = usize::try_from(header.lump_offset).map_err(|_| out_of_bounds())?;
+        let length = usize::try_from(header.lump_length).map_err(|_| out_of_bounds())?;
+        let end = start.checked_add(length).ok_or_else(out_of_bounds)?;
+        let lump_data = data.get(start..end).ok_or_else(out_of_bounds)?;
+
+        self.lump_overrides.insert(
+            lump,
+            LumpOverride {
+                version: Some(header.lump_version as u16),
+                data: Cow::Borrowed(lump_data),
+            },
+        );
+        Ok(())
+    }
+
+    /// Open the embedded pakfile lump, which bundles the custom materials, models and textures
+    /// the map depends on, as a navigable zip archive.
+    pub fn pak_file(&self) -> BspResult<zip::ZipArchive<Cursor<Cow<[u8]>>>> {
+        let data = self.get_lump(LumpType::PakFile)?;
+        open_pak_archive(data)
+    }
+
+    /// List the paths of every entry in the embedded pakfile lump.
+    pub fn pak_file_entries(&self) -> BspResult<Vec<String>> {
+        let archive = self.pak_file()?;
+        Ok(archive.file_names().map(String::from).collect())
+    }
+
+    /// Read a single entry from the embedded pakfile lump by its path.
+    pub fn read_pak_file_entry(&self, path: &str) -> BspResult<Vec<u8>> {
+        let mut archive = self.pak_file()?;
+        let mut file = archive.by_name(path)?;
+        // `file.size()` is untrusted map data; cap it rather than trusting it outright.
+        let prealloc = (file.size() as usize).min(MAX_PAK_ENTRY_PREALLOC);
+        let mut buf = Vec::with_capacity(prealloc);
+        file.read_to_end(&mut buf)?;
+        Ok(buf)
+    }
+
+    /// The map checksum servers use to verify clients loaded the same map, matching
+    /// Source's `CRC_MapFile`. Computed over every lump except the entities lump, since
+    /// that one varies per server.
+    pub fn crc32(&self) -> u32 {
+        let mut crc = 0xFFFF_FFFFu32;
+        for &lump in LumpType::ALL.iter() {
+            if lump == LumpType::Entities {
+                continue;
+            }
+            if let Ok(data) = self.get_lump(lump) {
+                crc = crc32_fold(crc, &data);
+            }
+        }
+        !crc
+    }
+
+    /// Parse the `Leaves` lump. The layout is version-sensitive: versions before 1 embed a
+    /// compressed ambient-lighting cube directly in each leaf, while 1 and later moved that
+    /// data out into the separate `LeafAmbientLighting`/`LeafAmbientIndex` lumps.
+    // TODO: `Faces`/`OriginalFaces` are similarly version-sensitive but don't have an accessor
+    // here; the struct they'd parse into lives outside this chunk of the crate.
+    pub fn leaves(&self) -> BspResult<Vec<Leaf>> {
+        let data = self.get_lump(LumpType::Leaves)?;
+        let version = self.lump_version(LumpType::Leaves);
+        let len = data.len() as u64;
+        let mut cursor = Cursor::new(data.as_ref());
+        let mut leaves = Vec::new();
+        while cursor.position() < len {
+            leaves.push(cursor.read_type_args(self.endian, (version,))?);
+        }
+        Ok(leaves)
+    }
 }
 
 #[allow(dead_code)]
 #[repr(C)]
 #[derive(Clone, Copy, Debug, Eq, PartialEq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
 pub enum LumpType {
     Entities,
     Planes,
@@ -134,3 +309,480 @@ pub enum LumpType {
 }
 
 static_assertions::const_assert_eq!(LumpType::DisplacementMultiBlend as usize, 63);
+
+impl LumpType {
+    /// Every lump type, in directory order.
+    const ALL: [LumpType; 64] = [
+        LumpType::Entities,
+        LumpType::Planes,
+        LumpType::TextureData,
+        LumpType::Vertices,
+        LumpType::Visibility,
+        LumpType::Nodes,
+        LumpType::TextureInfo,
+        LumpType::Faces,
+        LumpType::Lighting,
+        LumpType::Occlusion,
+        LumpType::Leaves,
+        LumpType::FaceIds,
+        LumpType::Edges,
+        LumpType::SurfaceEdges,
+        LumpType::Models,
+        LumpType::WorldLights,
+        LumpType::LeafFaces,
+        LumpType::LeafBrushes,
+        LumpType::Brushes,
+        LumpType::BrushSides,
+        LumpType::Areas,
+        LumpType::AreaPortals,
+        LumpType::Unused0,
+        LumpType::Unused1,
+        LumpType::Unused2,
+        LumpType::Unused3,
+        LumpType::DisplacementInfo,
+        LumpType::OriginalFaces,
+        LumpType::PhysDisplacement,
+        LumpType::PhysCollide,
+        LumpType::VertNormals,
+        LumpType::VertNormalIndices,
+        LumpType::DisplacementLightMapAlphas,
+        LumpType::DisplacementVertices,
+        LumpType::DisplacementLightMapSamplePositions,
+        LumpType::GameLump,
+        LumpType::LeafWaterData,
+        LumpType::Primitives,
+        LumpType::PrimVertices,
+        LumpType::PrimIndices,
+        LumpType::PakFile,
+        LumpType::ClipPortalVertices,
+        LumpType::CubeMaps,
+        LumpType::TextureDataStringData,
+        LumpType::TextureDataStringTable,
+        LumpType::Overlays,
+        LumpType::LeafMinimumDistanceToWater,
+        LumpType::FaceMacroTextureInfo,
+        LumpType::DisplacementTris,
+        LumpType::PhysicsCollideSurface,
+        LumpType::WaterOverlays,
+        LumpType::LeafAmbientIndexHdr,
+        LumpType::LeafAmbientIndex,
+        LumpType::LightingHdr,
+        LumpType::WorldLightsHdr,
+        LumpType::LeafAmbientLightingHdr,
+        LumpType::LeafAmbientLighting,
+        LumpType::XZipPakFile,
+        LumpType::FacesHdr,
+        LumpType::MapFlags,
+        LumpType::OverlayFades,
+        LumpType::OverlaySystemLevels,
+        LumpType::PhysLevel,
+        LumpType::DisplacementMultiBlend,
+    ];
+}
+
+/// Build the reflected CRC-32 table used by `BspFile::crc32`, matching Source's checksum_crc.h.
+const fn crc32_table() -> [u32; 256] {
+    let mut table = [0u32; 256];
+    let mut i = 0;
+    while i < 256 {
+        let mut crc = i as u32;
+        let mut j = 0;
+        while j < 8 {
+            crc = if crc & 1 != 0 {
+                (crc >> 1) ^ 0xEDB8_8320
+            } else {
+                crc >> 1
+            };
+            j += 1;
+        }
+        table[i] = crc;
+        i += 1;
+    }
+    table
+}
+
+const CRC32_TABLE: [u32; 256] = crc32_table();
+
+/// Fold `data` into a running reflected CRC-32 accumulator.
+fn crc32_fold(mut crc: u32, data: &[u8]) -> u32 {
+    for &byte in data {
+        crc = (crc >> 8) ^ CRC32_TABLE[((crc ^ byte as u32) & 0xFF) as usize];
+    }
+    crc
+}
+
+/// Compute a standalone CRC-32 over a byte slice (init/final-NOT included), matching the
+/// standard reflected CRC-32 used by `BspFile::crc32`.
+#[allow(dead_code)]
+fn crc32_bytes(data: &[u8]) -> u32 {
+    !crc32_fold(0xFFFF_FFFF, data)
+}
+
+fn open_pak_archive(data: Cow<[u8]>) -> BspResult<zip::ZipArchive<Cursor<Cow<[u8]>>>> {
+    Ok(zip::ZipArchive::new(Cursor::new(data))?)
+}
+
+/// Header of an external `.lmp` lump file, giving the single lump it carries a replacement
+/// for, the version to parse it as, and where in the file its data lives.
+#[derive(Debug, Clone, Copy, binrw::BinRead)]
+struct LumpFileHeader {
+    pub lump_offset: i32,
+    pub lump_id: i32,
+    pub lump_version: i32,
+    pub lump_length: i32,
+}
+
+/// A BSP leaf node.
+#[derive(Debug, Clone, BinRead)]
+#[br(import(version: u16))]
+pub struct Leaf {
+    pub contents: i32,
+    pub cluster: i16,
+    /// `area: 9 bits, flags: 7 bits`, kept packed like the engine's bitfield.
+    pub area_flags: i16,
+    pub mins: [i16; 3],
+    pub maxs: [i16; 3],
+    pub first_leaf_face: u16,
+    pub num_leaf_faces: u16,
+    pub first_leaf_brush: u16,
+    pub num_leaf_brushes: u16,
+    pub leaf_water_data_id: i16,
+    // Removed after version 0: ambient lighting moved out into its own lump. The 2-byte pad
+    // trails the struct either way, to keep every leaf a multiple of 4 bytes.
+    #[br(if(version == 0))]
+    pub ambient_lighting: Option<CompressedLightCube>,
+    _pad: [u8; 2],
+}
+
+#[derive(Debug, Clone, Copy, BinRead)]
+pub struct CompressedLightCube {
+    pub faces: [ColorRgbExp32; 6],
+}
+
+#[derive(Debug, Clone, Copy, BinRead)]
+pub struct ColorRgbExp32 {
+    pub r: u8,
+    pub g: u8,
+    pub b: u8,
+    pub exponent: i8,
+}
+
+/// Assemble a minimal but real little-endian `.bsp` byte buffer: a "VBSP" header, version 20, a
+/// 64-entry directory pointing at `lumps` (each `(type, version, data)`), and the lump bytes
+/// themselves. Lets tests exercise `BspFile::new` and friends against real parsing instead of
+/// stand-ins.
+#[cfg(test)]
+fn build_bsp_bytes(lumps: &[(LumpType, u16, &[u8])]) -> Vec<u8> {
+    build_bsp_bytes_endian(lumps, Endian::Little)
+}
+
+/// Like `build_bsp_bytes`, but in `endian` byte order: `Endian::Big` writes the byte-swapped
+/// "PSBV" ident and big-endian multi-byte fields, the way a console BSP does.
+#[cfg(test)]
+fn build_bsp_bytes_endian(lumps: &[(LumpType, u16, &[u8])], endian: Endian) -> Vec<u8> {
+    const DIRECTORY_ENTRY_LEN: usize = 16;
+    const HEADER_LEN: usize = 4 + 4 + LumpType::ALL.len() * DIRECTORY_ENTRY_LEN + 4;
+
+    let write_i32 = |bytes: &mut Vec<u8>, v: i32| {
+        bytes.extend_from_slice(&match endian {
+            Endian::Big => v.to_be_bytes(),
+            Endian::Little => v.to_le_bytes(),
+        });
+    };
+
+    let mut entries = [(0i32, 0i32, 0i32, 0i32); LumpType::ALL.len()];
+    let mut body = Vec::new();
+    for (lump, version, data) in lumps {
+        let offset = (HEADER_LEN + body.len()) as i32;
+        entries[*lump as usize] = (offset, data.len() as i32, *version as i32, 0);
+        body.extend_from_slice(data);
+    }
+
+    let mut bytes = Vec::with_capacity(HEADER_LEN + body.len());
+    bytes.extend_from_slice(match endian {
+        Endian::Big => b"PSBV",
+        Endian::Little => b"VBSP",
+    });
+    write_i32(&mut bytes, 20);
+    for (offset, length, version, ident) in entries {
+        write_i32(&mut bytes, offset);
+        write_i32(&mut bytes, length);
+        write_i32(&mut bytes, version);
+        write_i32(&mut bytes, ident);
+    }
+    write_i32(&mut bytes, 0); // map revision
+    bytes.extend_from_slice(&body);
+    bytes
+}
+
+#[test]
+fn test_crc32_known_vector() {
+    // standard CRC-32 check value for the ASCII digits "123456789"
+    assert_eq!(crc32_bytes(b"123456789"), 0xCBF4_3926);
+}
+
+#[test]
+fn test_detect_endian() {
+    let little = Header {
+        v: 0x56,
+        b: 0x42,
+        s: 0x53,
+        p: 0x50,
+    };
+    let big = Header {
+        v: 0x50,
+        b: 0x53,
+        s: 0x42,
+        p: 0x56,
+    };
+    let garbage = Header {
+        v: 0,
+        b: 0,
+        s: 0,
+        p: 0,
+    };
+
+    assert!(matches!(detect_endian(little), Ok(Endian::Little)));
+    assert!(matches!(detect_endian(big), Ok(Endian::Big)));
+    assert!(detect_endian(garbage).is_err());
+}
+
+#[test]
+fn test_open_pak_archive_lists_entries() {
+    let mut buf = Vec::new();
+    {
+        let mut writer = zip::ZipWriter::new(Cursor::new(&mut buf));
+        writer
+            .start_file("materials/test.vmt", zip::write::FileOptions::default())
+            .unwrap();
+        writer.write_all(b"test material").unwrap();
+        writer.finish().unwrap();
+    }
+
+    let mut archive = open_pak_archive(Cow::Owned(buf)).unwrap();
+    assert_eq!(archive.file_names().collect::<Vec<_>>(), ["materials/test.vmt"]);
+
+    let mut file = archive.by_name("materials/test.vmt").unwrap();
+    let mut contents = Vec::new();
+    file.read_to_end(&mut contents).unwrap();
+    assert_eq!(contents, b"test material");
+}
+
+#[test]
+fn test_lump_override_takes_precedence_over_directory() {
+    let bytes = build_bsp_bytes(&[
+        (LumpType::Entities, 0, b"directory entities"),
+        (LumpType::Planes, 0, b"planes"),
+    ]);
+    let mut bsp = BspFile::new(&bytes).unwrap();
+
+    assert_eq!(&*bsp.get_lump(LumpType::Entities).unwrap(), b"directory entities");
+
+    bsp.with_lump_override(LumpType::Entities, b"patched entities");
+    assert_eq!(&*bsp.get_lump(LumpType::Entities).unwrap(), b"patched entities");
+
+    // lumps without an override still come from the directory
+    assert_eq!(&*bsp.get_lump(LumpType::Planes).unwrap(), b"planes");
+}
+
+/// Raw bytes for one `Leaf`, with or without the version-0 ambient-lighting block, in `endian`
+/// byte order.
+#[cfg(test)]
+fn leaf_bytes(ambient: bool, endian: Endian) -> Vec<u8> {
+    let write_i16 = |bytes: &mut Vec<u8>, v: i16| {
+        bytes.extend_from_slice(&match endian {
+            Endian::Big => v.to_be_bytes(),
+            Endian::Little => v.to_le_bytes(),
+        });
+    };
+    let write_u16 = |bytes: &mut Vec<u8>, v: u16| {
+        bytes.extend_from_slice(&match endian {
+            Endian::Big => v.to_be_bytes(),
+            Endian::Little => v.to_le_bytes(),
+        });
+    };
+
+    let mut bytes = Vec::new();
+    bytes.extend_from_slice(&match endian {
+        Endian::Big => 1i32.to_be_bytes(),
+        Endian::Little => 1i32.to_le_bytes(),
+    }); // contents
+    write_i16(&mut bytes, 2); // cluster
+    write_i16(&mut bytes, 3); // area_flags
+    for v in [4i16, 5, 6] {
+        write_i16(&mut bytes, v); // mins
+    }
+    for v in [7i16, 8, 9] {
+        write_i16(&mut bytes, v); // maxs
+    }
+    write_u16(&mut bytes, 10); // first_leaf_face
+    write_u16(&mut bytes, 11); // num_leaf_faces
+    write_u16(&mut bytes, 12); // first_leaf_brush
+    write_u16(&mut bytes, 13); // num_leaf_brushes
+    write_i16(&mut bytes, 14); // leaf_water_data_id
+    if ambient {
+        for _ in 0..6 {
+            bytes.extend_from_slice(&[5u8, 6, 7, 8]); // r, g, b, exponent
+        }
+    }
+    bytes.extend_from_slice(&[0u8, 0]); // pad
+    bytes
+}
+
+#[test]
+fn test_leaves_version_zero_includes_ambient_lighting() {
+    let lump = leaf_bytes(true, Endian::Little);
+    let bytes = build_bsp_bytes(&[(LumpType::Leaves, 0, &lump)]);
+    let bsp = BspFile::new(&bytes).unwrap();
+
+    let leaves = bsp.leaves().unwrap();
+    assert_eq!(leaves.len(), 1);
+    let ambient = leaves[0].ambient_lighting.as_ref().unwrap();
+    assert_eq!(ambient.faces[0].r, 5);
+    assert_eq!(ambient.faces[5].exponent, 8);
+}
+
+#[test]
+fn test_leaves_version_one_has_no_ambient_lighting_and_stays_in_sync() {
+    let mut lump = leaf_bytes(false, Endian::Little);
+    lump.extend_from_slice(&leaf_bytes(false, Endian::Little));
+    let bytes = build_bsp_bytes(&[(LumpType::Leaves, 1, &lump)]);
+    let bsp = BspFile::new(&bytes).unwrap();
+
+    let leaves = bsp.leaves().unwrap();
+    assert_eq!(leaves.len(), 2);
+    assert!(leaves[0].ambient_lighting.is_none());
+    assert!(leaves[1].ambient_lighting.is_none());
+    assert_eq!(leaves[1].leaf_water_data_id, 14);
+}
+
+/// Assemble a real `.lmp` lump file in `endian` byte order: the 16-byte `LumpFileHeader`
+/// followed by `data`, with the header's offset/length pointing at that trailing data.
+#[cfg(test)]
+fn build_lmp_bytes(lump_id: i32, lump_version: i32, data: &[u8], endian: Endian) -> Vec<u8> {
+    const HEADER_LEN: i32 = 16;
+
+    let write_i32 = |bytes: &mut Vec<u8>, v: i32| {
+        bytes.extend_from_slice(&match endian {
+            Endian::Big => v.to_be_bytes(),
+            Endian::Little => v.to_le_bytes(),
+        });
+    };
+
+    let mut bytes = Vec::with_capacity(HEADER_LEN as usize + data.len());
+    write_i32(&mut bytes, HEADER_LEN); // lump_offset
+    write_i32(&mut bytes, lump_id);
+    write_i32(&mut bytes, lump_version);
+    write_i32(&mut bytes, data.len() as i32); // lump_length
+    bytes.extend_from_slice(data);
+    bytes
+}
+
+#[test]
+fn test_load_lump_file_round_trips_data_and_version() {
+    let bytes = build_bsp_bytes(&[(LumpType::Leaves, 0, &leaf_bytes(true, Endian::Little))]);
+    let mut bsp = BspFile::new(&bytes).unwrap();
+
+    // The directory says version 0 (ambient lighting embedded); the .lmp override claims
+    // version 1 (no ambient lighting) for its single leaf.
+    let patched_leaf = leaf_bytes(false, Endian::Little);
+    let lmp = build_lmp_bytes(LumpType::Leaves as i32, 1, &patched_leaf, Endian::Little);
+    bsp.load_lump_file(&lmp).unwrap();
+
+    assert_eq!(&*bsp.get_lump(LumpType::Leaves).unwrap(), patched_leaf.as_slice());
+
+    let leaves = bsp.leaves().unwrap();
+    assert_eq!(leaves.len(), 1);
+    assert!(leaves[0].ambient_lighting.is_none());
+}
+
+#[test]
+fn test_load_lump_file_rejects_out_of_bounds_offset() {
+    let bytes = build_bsp_bytes(&[]);
+    let mut bsp = BspFile::new(&bytes).unwrap();
+
+    let mut lmp = build_lmp_bytes(LumpType::Entities as i32, 0, b"entities", Endian::Little);
+    // Overwrite the header's offset with one far past the end of the file, so the bounds check
+    // rejects it instead of taking the in-bounds happy path.
+    lmp[0..4].copy_from_slice(&i32::MAX.to_le_bytes());
+
+    assert!(matches!(
+        bsp.load_lump_file(&lmp),
+        Err(BspError::LumpFileOutOfBounds { .. })
+    ));
+}
+
+#[test]
+fn test_crc32_excludes_entities_lump() {
+    let with_one_entities = build_bsp_bytes(&[
+        (LumpType::Entities, 0, b"entities one"),
+        (LumpType::Planes, 0, b"planes"),
+    ]);
+    let with_other_entities = build_bsp_bytes(&[
+        (LumpType::Entities, 0, b"a completely different entity lump"),
+        (LumpType::Planes, 0, b"planes"),
+    ]);
+
+    let crc_one = BspFile::new(&with_one_entities).unwrap().crc32();
+    let crc_other = BspFile::new(&with_other_entities).unwrap().crc32();
+
+    assert_eq!(crc_one, crc_other);
+}
+
+#[test]
+fn test_pak_file_entries_and_read_entry_through_public_api() {
+    let mut zip_bytes = Vec::new();
+    {
+        let mut writer = zip::ZipWriter::new(Cursor::new(&mut zip_bytes));
+        writer
+            .start_file("materials/test.vmt", zip::write::FileOptions::default())
+            .unwrap();
+        writer.write_all(b"test material").unwrap();
+        writer.finish().unwrap();
+    }
+
+    let bsp_bytes = build_bsp_bytes(&[(LumpType::PakFile, 0, &zip_bytes)]);
+    let bsp = BspFile::new(&bsp_bytes).unwrap();
+
+    assert_eq!(bsp.pak_file_entries().unwrap(), ["materials/test.vmt"]);
+    assert_eq!(
+        bsp.read_pak_file_entry("materials/test.vmt").unwrap(),
+        b"test material"
+    );
+}
+
+#[test]
+fn test_big_endian_console_bsp_round_trips_through_new_and_leaves() {
+    let lump = leaf_bytes(true, Endian::Big);
+    let bytes = build_bsp_bytes_endian(&[(LumpType::Leaves, 0, &lump)], Endian::Big);
+    let bsp = BspFile::new(&bytes).unwrap();
+
+    assert_eq!(bsp.endian(), Endian::Big);
+    assert_eq!(bsp.version(), 20);
+
+    let leaves = bsp.leaves().unwrap();
+    assert_eq!(leaves.len(), 1);
+    let ambient = leaves[0].ambient_lighting.as_ref().unwrap();
+    assert_eq!(ambient.faces[0].r, 5);
+    assert_eq!(ambient.faces[5].exponent, 8);
+}
+
+#[test]
+fn test_big_endian_console_bsp_load_lump_file_uses_read_be() {
+    let lump = leaf_bytes(true, Endian::Big);
+    let bytes = build_bsp_bytes_endian(&[(LumpType::Leaves, 0, &lump)], Endian::Big);
+    let mut bsp = BspFile::new(&bytes).unwrap();
+
+    // directory says version 0 (ambient lighting embedded); the big-endian .lmp override claims
+    // version 1 (no ambient lighting) instead, so this also exercises the version threading from
+    // the `.lmp` header through `load_lump_file`'s `read_be` branch.
+    let patched_leaf = leaf_bytes(false, Endian::Big);
+    let lmp = build_lmp_bytes(LumpType::Leaves as i32, 1, &patched_leaf, Endian::Big);
+    bsp.load_lump_file(&lmp).unwrap();
+
+    assert_eq!(&*bsp.get_lump(LumpType::Leaves).unwrap(), patched_leaf.as_slice());
+
+    let leaves = bsp.leaves().unwrap();
+    assert_eq!(leaves.len(), 1);
+    assert!(leaves[0].ambient_lighting.is_none());
+}
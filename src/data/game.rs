@@ -18,6 +18,7 @@ impl GameLumpHeader {
     pub fn find<T: GameLumpType<Args<'static> = (u16,)>>(
         &self,
         data: &[u8],
+        endian: Endian,
     ) -> Option<Result<T, BspError>> {
         let (i, lump) = self
             .lumps
@@ -30,7 +31,11 @@ impl GameLumpHeader {
             Err(e) => return Some(Err(e)),
         };
         let mut reader = Cursor::new(data);
-        Some(reader.read_le_args((lump.version,)).map_err(BspError::from))
+        Some(
+            reader
+                .read_type_args(endian, (lump.version,))
+                .map_err(BspError::from),
+        )
     }
 
     fn get_game_lump_data<'a>(
@@ -62,6 +67,7 @@ impl GameLumpHeader {
 }
 
 #[derive(Debug, Clone, BinRead)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
 pub struct GameLump {
     pub id: i32,
     pub flags: GameLumpFlags,
@@ -79,6 +85,31 @@ bitflags! {
     }
 }
 
+#[cfg(feature = "serde")]
+impl serde::Serialize for GameLumpFlags {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        use serde::ser::SerializeSeq;
+
+        let mut seq = serializer.serialize_seq(None)?;
+        for (name, _) in self.iter_names() {
+            seq.serialize_element(name)?;
+        }
+        seq.end()
+    }
+}
+
+#[cfg(feature = "serde")]
+#[test]
+fn test_game_lump_flags_serialize_as_bit_names() {
+    assert_eq!(
+        serde_json::to_string(&GameLumpFlags::COMPRESSED).unwrap(),
+        r#"["COMPRESSED"]"#
+    );
+}
+
 pub trait GameLumpType: BinRead {
     const ID: i32;
 }
@@ -118,10 +149,12 @@ pub struct StaticPropLumps {
     pub props: Vec<StaticPropLump>,
 }
 
+// Not `Serialize`-able yet under the `serde` feature: `origin`/`lighting_origin` are `Vector`,
+// which lives outside this chunk and has no derive of its own.
 #[derive(Debug, Clone)]
 pub struct StaticPropLump {
     pub origin: Vector,
-    angles: [f32; 3],
+    pub angles: [f32; 3],
     pub prop_type: u16,
     pub first_leaf: u16,
     pub leaf_count: u16,
@@ -186,8 +219,35 @@ bitflags! {
     }
 }
 
+#[cfg(feature = "serde")]
+impl serde::Serialize for StaticPropLumpFlags {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        use serde::ser::SerializeSeq;
+
+        let mut seq = serializer.serialize_seq(None)?;
+        for (name, _) in self.iter_names() {
+            seq.serialize_element(name)?;
+        }
+        seq.end()
+    }
+}
+
+#[cfg(feature = "serde")]
+#[test]
+fn test_static_prop_lump_flags_serialize_as_bit_names() {
+    let flags = StaticPropLumpFlags::FLAG_FADES | StaticPropLumpFlags::NO_DRAW;
+    assert_eq!(
+        serde_json::to_string(&flags).unwrap(),
+        r#"["FLAG_FADES","NO_DRAW"]"#
+    );
+}
+
 #[repr(u8)]
 #[derive(BinRead, Debug, Copy, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
 #[br(repr = u8)]
 pub enum SolidType {
     None = 0,
@@ -39,6 +39,10 @@ pub enum BspError {
     LumpVersion(UnsupportedLumpVersion),
     #[error(transparent)]
     Zip(#[from] ZipError),
+    #[error("lump id {0} in external lump file does not match any known lump type")]
+    UnknownLumpFileId(i32),
+    #[error("external lump file data is out of bounds (offset {offset}, length {length})")]
+    LumpFileOutOfBounds { offset: i32, length: i32 },
 }
 
 impl From<binrw::Error> for BspError {